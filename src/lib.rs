@@ -1,7 +1,14 @@
 //! Macros to work with `std::collections` that are mostly straight-forward syntactic sugars.
+pub use std::collections::BTreeMap;
+pub use std::collections::BTreeSet;
+pub use std::collections::BinaryHeap;
 pub use std::collections::HashMap;
 pub use std::collections::HashSet;
 
+#[cfg(feature = "rayon")]
+#[doc(hidden)]
+pub use rayon;
+
 /// Counts the number of `args` passed to this macro invocation.
 ///
 /// Returns the count as `usize`.
@@ -90,6 +97,45 @@ macro_rules! hashmap {
     }};
 }
 
+/// Just like `hashmap!`, but takes a `BuildHasher` instead of using the default `RandomState`.
+///
+/// This is for when you want to drop in a faster or DoS-resistant hasher (anything
+/// `S: BuildHasher + Default`) while keeping the exact-preallocation optimization that
+/// `hashmap!` already does via `count_args!`.
+///
+/// ```
+/// #[macro_use] extern crate colmac;
+///
+/// use std::collections::HashMap;
+/// use std::collections::hash_map::RandomState;
+///
+/// // create an empty one
+/// let empty: HashMap<u64, u64, RandomState> = hashmap_with_hasher![RandomState::new();];
+/// assert_eq!(0, empty.len());
+///
+/// // literal initialization
+/// let mut map_a = HashMap::new();
+/// map_a.insert("a", 123);
+/// map_a.insert("b", 456);
+///
+/// let map_b = hashmap_with_hasher![RandomState::new(); "a" => 123, "b" => 456];
+/// assert_eq!(map_a, map_b);
+/// ```
+#[macro_export]
+macro_rules! hashmap_with_hasher {
+    ( $hasher:expr; ) => {
+        HashMap::with_hasher($hasher)
+    };
+    ( $hasher:expr; $( $key:expr => $value:expr ),* ) => {{
+        let size = count_args!( $( $key ),* );
+        let mut map = HashMap::with_capacity_and_hasher(size, $hasher);
+        $(
+            map.insert($key, $value);
+        )*
+        map
+    }};
+}
+
 /// Just like `vec!`, but for `std::collections::HashSet`.
 ///
 /// This macro uses `count_args!` to preallocate the exact amount of memory
@@ -127,6 +173,206 @@ macro_rules! hashset {
     }};
 }
 
+/// Just like `vec!`, but for `std::collections::BTreeMap`.
+///
+/// `BTreeMap` has no `with_capacity`, so unlike [`hashmap!`] this simply inserts the
+/// key-value pairs in order. The payoff is sorted iteration and range queries on the
+/// resulting map.
+///
+/// ```
+/// #[macro_use] extern crate colmac;
+///
+/// use std::collections::BTreeMap;
+///
+/// // create an empty one
+/// let empty: BTreeMap<u64, u64> = btreemap![];
+/// assert_eq!(0, empty.len());
+///
+/// // literal initialization
+/// let mut map_a = BTreeMap::new();
+/// map_a.insert("a", 123);
+/// map_a.insert("b", 456);
+///
+/// let map_b = btreemap!["a" => 123, "b" => 456];
+/// assert_eq!(map_a, map_b);
+/// ```
+#[macro_export]
+macro_rules! btreemap {
+    () => {
+        BTreeMap::new()
+    };
+    ( $( $key:expr => $value:expr ),* ) => {{
+        let mut map = BTreeMap::new();
+        $(
+            map.insert($key, $value);
+        )*
+        map
+    }};
+}
+
+/// Just like `vec!`, but for `std::collections::BTreeSet`.
+///
+/// `BTreeSet` has no `with_capacity`, so unlike [`hashset!`] this simply inserts the
+/// elements in order. The payoff is sorted iteration and range queries on the resulting
+/// set.
+///
+/// ```
+/// #[macro_use] extern crate colmac;
+///
+/// use std::collections::BTreeSet;
+///
+/// // create an empty one
+/// let empty: BTreeSet<u64> = btreeset![];
+/// assert_eq!(0, empty.len());
+///
+/// // literal initialization
+/// let mut set_a = BTreeSet::new();
+/// set_a.insert(123);
+/// set_a.insert(456);
+///
+/// let set_b = btreeset!(123, 456);
+/// assert_eq!(set_a, set_b);
+/// ```
+#[macro_export]
+macro_rules! btreeset {
+    () => {
+        BTreeSet::new()
+    };
+    ( $( $elem:expr ),* ) => {{
+        let mut set = BTreeSet::new();
+        $(
+            set.insert($elem);
+        )*
+        set
+    }};
+}
+
+/// Just like `hashset!`, but takes a `BuildHasher` instead of using the default `RandomState`.
+///
+/// This is for when you want to drop in a faster or DoS-resistant hasher (anything
+/// `S: BuildHasher + Default`) while keeping the exact-preallocation optimization that
+/// `hashset!` already does via `count_args!`.
+///
+/// ```
+/// #[macro_use] extern crate colmac;
+///
+/// use std::collections::HashSet;
+/// use std::collections::hash_map::RandomState;
+///
+/// // create an empty one
+/// let empty: HashSet<u64, RandomState> = hashset_with_hasher![RandomState::new();];
+/// assert_eq!(0, empty.len());
+///
+/// // literal initialization
+/// let mut set_a = HashSet::new();
+/// set_a.insert(123);
+/// set_a.insert(456);
+///
+/// let set_b = hashset_with_hasher!(RandomState::new(); 123, 456);
+/// assert_eq!(set_a, set_b);
+/// ```
+#[macro_export]
+macro_rules! hashset_with_hasher {
+    ( $hasher:expr; ) => {
+        HashSet::with_hasher($hasher)
+    };
+    ( $hasher:expr; $( $elem:expr ),* ) => {{
+        let size = count_args!( $($elem),* );
+        let mut set = HashSet::with_capacity_and_hasher(size, $hasher);
+        $(
+            set.insert($elem);
+        )*
+        set
+    }};
+}
+
+/// Just like `vec!`, but for `std::collections::BinaryHeap`.
+///
+/// This macro uses `count_args!` to preallocate the exact amount of memory needed, so
+/// it's more efficient than simply iteratively pushing.
+///
+/// ```
+/// #[macro_use] extern crate colmac;
+///
+/// use std::collections::BinaryHeap;
+///
+/// // create an empty one
+/// let empty: BinaryHeap<u64> = binaryheap![];
+/// assert_eq!(0, empty.len());
+///
+/// // literal initialization
+/// let mut heap_a = BinaryHeap::new();
+/// heap_a.push(4);
+/// heap_a.push(1);
+/// heap_a.push(7);
+///
+/// let heap_b = binaryheap![4, 1, 7];
+/// assert_eq!(heap_a.into_sorted_vec(), heap_b.into_sorted_vec());
+/// ```
+#[macro_export]
+macro_rules! binaryheap {
+    () => {
+        BinaryHeap::new()
+    };
+    ( $( $elem:expr ),* ) => {{
+        let size = count_args!( $($elem),* );
+        let mut heap = BinaryHeap::with_capacity(size);
+        $(
+            heap.push($elem);
+        )*
+        heap
+    }};
+}
+
+/// Tallies an iterable into a `HashMap` from each distinct element to its count.
+///
+/// There are two ways to invoke this macro:
+/// 1. with one argument, an `IntoIterator`
+///     1. maps each distinct element to the number of times it occurs, via
+///        `*map.entry(x).or_default() += 1`
+/// 1. with two arguments, an `IntoIterator` followed by a weight function
+///     1. maps each distinct element to the sum of `weight_fn` applied to each of its
+///        occurrences, rather than a plain count
+///
+/// Pairs naturally with [`sorted!`] for producing ranked frequency lists.
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate colmac;
+///
+/// use std::collections::HashMap;
+///
+/// // plain counting
+/// let counts = counter!(vec!["a", "b", "a", "c", "b", "a"]);
+/// assert_eq!(3, counts["a"]);
+/// assert_eq!(2, counts["b"]);
+/// assert_eq!(1, counts["c"]);
+///
+/// // weighted counting
+/// let weighted = counter!(vec!["a", "b", "a"] => |_| 2usize);
+/// assert_eq!(4usize, weighted["a"]);
+/// assert_eq!(2usize, weighted["b"]);
+/// ```
+#[macro_export]
+macro_rules! counter {
+    ( $iter:expr ) => {{
+        let mut map = HashMap::new();
+        for x in $iter.into_iter() {
+            *map.entry(x).or_default() += 1;
+        }
+        map
+    }};
+    ( $iter:expr => $weight_fn:expr ) => {{
+        let mut map = HashMap::new();
+        for x in $iter.into_iter() {
+            let weight = $weight_fn(&x);
+            *map.entry(x).or_default() += weight;
+        }
+        map
+    }};
+}
+
 /// Sorts the input collection that impl's the trait `std::ops::IndexMut`.
 ///
 /// There are two ways to invoke this macro:
@@ -239,6 +485,102 @@ macro_rules! sorted_f64 {
     };
 }
 
+/// Just like `sort!`, but sorts across threads using rayon's
+/// [`par_sort_unstable`](https://docs.rs/rayon/latest/rayon/slice/trait.ParallelSliceMut.html#method.par_sort_unstable).
+///
+/// Requires the `rayon` feature. The invocation shapes are identical to `sort!`, so
+/// switching to parallel sorting for a large collection is a one-token change.
+///
+/// There are two ways to invoke this macro:
+/// 1. with one argument, a mutable collection
+///     1. uses `par_sort_unstable` to sort
+/// 1. with two arguments, a mutable collection followed by a closure
+///     1. passes the closure to `par_sort_unstable_by` to sort
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate colmac;
+/// use std::cmp::Ordering::{Equal, Greater, Less};
+///
+/// // sort without a custom closure
+/// let mut v1 = vec![2, 4, -1];
+/// par_sort!(v1);
+/// assert_eq!(vec![-1, 2, 4], v1);
+///
+/// // sort with; sort in reverse order
+/// let mut v2 = vec![2, 4, -1];
+/// par_sort!(v2, |a, b| match a.cmp(b) {
+///     Less => Greater,
+///     Greater => Less,
+///     Equal => Equal,
+/// });
+/// assert_eq!(vec![4, 2, -1], v2);
+/// ```
+#[cfg(feature = "rayon")]
+#[macro_export]
+macro_rules! par_sort {
+    ( $collection:expr ) => {{
+        use $crate::rayon::prelude::*;
+        (&mut $collection[..]).par_sort_unstable();
+    }};
+    ( $collection:expr, $compare_fn:expr ) => {{
+        use $crate::rayon::prelude::*;
+        (&mut $collection[..]).par_sort_unstable_by($compare_fn);
+    }};
+}
+
+/// Just like `sorted!`, but sorts across threads using rayon.
+///
+/// Requires the `rayon` feature. The input collection should support `.par_iter()`, and
+/// its elements must be `Clone + Send + Sync`. The original collection is left untouched,
+/// matching `sorted!`.
+///
+/// There are two ways to invoke this macro:
+/// 1. with one argument, a collection
+///     1. uses `par_sort_unstable` to sort
+/// 1. with two arguments, a collection followed by a closure
+///     1. passes the closure to `par_sort_unstable_by` to sort
+///
+/// # Examples
+///
+/// ```
+/// #[macro_use] extern crate colmac;
+/// use std::cmp::Ordering::{Equal, Greater, Less};
+///
+/// // sort without a custom closure
+/// let v1 = vec![2, 4, -1];
+/// let v1_sorted = par_sorted!(v1);
+/// assert_eq!(vec![2, 4, -1], v1);  // v1 is not modified
+/// assert_eq!(vec![-1, 2, 4], v1_sorted);
+///
+/// // sort with; sort in reverse order
+/// let v2 = vec![2, 4, -1];
+/// let v2_sorted = par_sorted!(v2, |a, b| match a.cmp(b) {
+///     Less => Greater,
+///     Greater => Less,
+///     Equal => Equal,
+/// });
+/// assert_eq!(vec![2, 4, -1], v2);  // v2 is not modified
+/// assert_eq!(vec![4, 2, -1], v2_sorted);
+/// ```
+#[cfg(feature = "rayon")]
+#[macro_export]
+macro_rules! par_sorted {
+    ( $collection:expr ) => {{
+        use $crate::rayon::prelude::*;
+        let mut clones: Vec<_> = $collection.par_iter().cloned().collect();
+        par_sort!(clones);
+        clones
+    }};
+    ( $collection:expr, $compare_fn:expr ) => {{
+        use $crate::rayon::prelude::*;
+        let mut clones: Vec<_> = $collection.par_iter().cloned().collect();
+        par_sort!(clones, $compare_fn);
+        clones
+    }};
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,6 +668,180 @@ mod tests {
         }
     }
 
+    mod hashmap_with_hasher {
+        use super::*;
+        use std::collections::hash_map::RandomState;
+
+        #[test]
+        fn zero() {
+            let expected: HashMap<usize, usize> = HashMap::new();
+            let result: HashMap<usize, usize> = hashmap_with_hasher!(RandomState::new(););
+            assert_eq!(expected, result);
+        }
+        #[test]
+        fn one() {
+            let key = "abcde";
+            let expected: HashMap<String, usize> =
+                [(string!(key), 3usize)].iter().cloned().collect();
+            let result: HashMap<String, usize> =
+                hashmap_with_hasher!(RandomState::new(); string!(key) => 3usize);
+            assert_eq!(expected, result);
+        }
+        #[test]
+        fn many() {
+            let expected: HashMap<String, usize> = vec![
+                (string!("a"), 10usize),
+                (string!("ab"), 20usize),
+                (string!("abc"), 30usize),
+            ]
+            .into_iter()
+            .collect();
+            let result = hashmap_with_hasher!(
+                RandomState::new();
+                string!("a") => 10usize,
+                string!("ab") => 20usize,
+                string!("abc") => 30usize
+            );
+            assert_eq!(expected, result);
+        }
+    }
+
+    mod hashset_with_hasher {
+        use super::*;
+        use std::collections::hash_map::RandomState;
+
+        #[test]
+        fn zero() {
+            let expected: HashSet<usize> = HashSet::new();
+            let result: HashSet<usize> = hashset_with_hasher!(RandomState::new(););
+            assert_eq!(expected, result);
+        }
+        #[test]
+        fn one() {
+            let name = string!("Jack");
+            let expected: HashSet<String> = vec![&name].into_iter().cloned().collect();
+            let result: HashSet<String> = hashset_with_hasher!(RandomState::new(); name);
+            assert_eq!(expected, result);
+        }
+        #[test]
+        fn many() {
+            let expected: HashSet<&str> = vec!["a", "b", "c"].into_iter().collect();
+            let result: HashSet<&str> = hashset_with_hasher!(RandomState::new(); "a", "b", "c");
+            assert_eq!(expected, result);
+        }
+    }
+
+    mod btreemap {
+        use super::*;
+
+        #[test]
+        fn zero() {
+            let expected: BTreeMap<usize, usize> = BTreeMap::new();
+            let result: BTreeMap<usize, usize> = btreemap!();
+            assert_eq!(expected, result);
+        }
+        #[test]
+        fn one() {
+            let key = "abcde";
+            let expected: BTreeMap<String, usize> =
+                [(string!(key), 3usize)].iter().cloned().collect();
+            let result: BTreeMap<String, usize> = btreemap!(string!(key) => 3usize);
+            assert_eq!(expected, result);
+        }
+        #[test]
+        fn many() {
+            let expected: BTreeMap<String, usize> = vec![
+                (string!("a"), 10usize),
+                (string!("ab"), 20usize),
+                (string!("abc"), 30usize),
+            ]
+            .into_iter()
+            .collect();
+            let result = btreemap!(
+                string!("a") => 10usize,
+                string!("ab") => 20usize,
+                string!("abc") => 30usize
+            );
+            assert_eq!(expected, result);
+        }
+    }
+
+    mod btreeset {
+        use super::*;
+
+        #[test]
+        fn zero() {
+            let expected: BTreeSet<usize> = BTreeSet::new();
+            let result: BTreeSet<usize> = btreeset!();
+            assert_eq!(expected, result);
+        }
+        #[test]
+        fn one() {
+            let name = string!("Jack");
+            let expected: BTreeSet<String> = vec![&name].into_iter().cloned().collect();
+            let result: BTreeSet<String> = btreeset!(name);
+            assert_eq!(expected, result);
+        }
+        #[test]
+        fn many() {
+            let expected: BTreeSet<&str> = vec!["a", "b", "c"].into_iter().collect();
+            let result: BTreeSet<&str> = btreeset!("a", "b", "c");
+            assert_eq!(expected, result);
+        }
+    }
+
+    mod binaryheap {
+        use super::*;
+
+        #[test]
+        fn zero() {
+            let expected: BinaryHeap<usize> = BinaryHeap::new();
+            let result: BinaryHeap<usize> = binaryheap!();
+            assert_eq!(expected.into_sorted_vec(), result.into_sorted_vec());
+        }
+        #[test]
+        fn one() {
+            let expected: BinaryHeap<usize> = vec![3usize].into_iter().collect();
+            let result: BinaryHeap<usize> = binaryheap!(3usize);
+            assert_eq!(expected.into_sorted_vec(), result.into_sorted_vec());
+        }
+        #[test]
+        fn many() {
+            let expected: BinaryHeap<i32> = vec![4, 1, 7].into_iter().collect();
+            let result: BinaryHeap<i32> = binaryheap!(4, 1, 7);
+            assert_eq!(expected.into_sorted_vec(), result.into_sorted_vec());
+        }
+    }
+
+    mod counter {
+        use super::*;
+
+        #[test]
+        fn empty() {
+            let expected: HashMap<usize, usize> = HashMap::new();
+            let result: HashMap<usize, usize> = counter!(Vec::<usize>::new());
+            assert_eq!(expected, result);
+        }
+        #[test]
+        fn counts_occurrences() {
+            let expected = hashmap!["a" => 3usize, "b" => 2usize, "c" => 1usize];
+            let result = counter!(vec!["a", "b", "a", "c", "b", "a"]);
+            assert_eq!(expected, result);
+        }
+        #[test]
+        fn weighted() {
+            let expected = hashmap!["a" => 4usize, "b" => 2usize];
+            let result = counter!(vec!["a", "b", "a"] => |_: &&str| 2usize);
+            assert_eq!(expected, result);
+        }
+        #[test]
+        fn weighted_float() {
+            let expected = hashmap!["a" => 3.0f64, "b" => 1.5f64];
+            let result = counter!(vec!["a", "b", "a"] => |_: &&str| 1.5f64);
+            assert_eq!(expected, result);
+        }
+    }
+
     mod sort {
         use super::*;
 
@@ -422,4 +938,58 @@ mod tests {
             assert_eq!(expected, result);
         }
     }
+
+    #[cfg(feature = "rayon")]
+    mod par_sort {
+        use super::*;
+
+        #[test]
+        fn vec() {
+            let expected = vec![-14, -1, 0, 2, 3, 4, 8];
+
+            let mut v = vec![4, 2, 3, -1, -14, 0, 8];
+            par_sort!(v);
+            assert_eq!(expected, v);
+        }
+        #[test]
+        fn vec_reverse_sort() {
+            let expected = vec![8, 4, 3, 2, 0, -1, -14];
+
+            let mut v = vec![4, 2, 3, -1, -14, 0, 8];
+            par_sort!(v, |a, b| match a.cmp(b) {
+                Less => Greater,
+                Greater => Less,
+                Equal => Equal,
+            });
+            assert_eq!(expected, v);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    mod par_sorted {
+        use super::*;
+
+        #[test]
+        fn vec() {
+            let expected = vec![-14, -1, 0, 2, 3, 4, 8];
+
+            let v = vec![4, 2, 3, -1, -14, 0, 8];
+            let result = par_sorted!(v);
+            assert_eq!(expected, result);
+            assert_eq!(vec![4, 2, 3, -1, -14, 0, 8], v); // unmodified
+        }
+        #[test]
+        fn vec_reverse_sort() {
+            let expected = vec![8, 4, 3, 2, 0, -1, -14];
+
+            let v = vec![4, 2, 3, -1, -14, 0, 8];
+            let result = par_sorted!(v, |a, b| match a.cmp(b) {
+                Less => Greater,
+                Greater => Less,
+                Equal => Equal,
+            });
+            assert_eq!(expected, result);
+            assert_eq!(vec![4, 2, 3, -1, -14, 0, 8], v); // unmodified
+        }
+    }
 }